@@ -0,0 +1,96 @@
+use log::warn;
+use playback_rs::{Player, Song};
+
+/// Sample rate used for the synthesized fallback tone.
+const TONE_SAMPLE_RATE: u32 = 44100;
+/// Default frequency, in Hz, of the synthesized fallback tone.
+pub const DEFAULT_TONE_FREQ_HZ: f32 = 440.0;
+/// Default duration, in seconds, of the synthesized fallback tone.
+pub const DEFAULT_TONE_DURATION_SECS: f32 = 0.3;
+/// Peak amplitude of the synthesized fallback tone; the user's volume setting is applied on top
+/// of it by the player, as for any other song.
+const TONE_VOLUME: f32 = 0.8;
+/// Length of the linear fade-in/fade-out applied to the fallback tone, to avoid audible clicks.
+const TONE_FADE: std::time::Duration = std::time::Duration::from_millis(10);
+
+/// Abstraction over the audio output, so the volume can be scaled
+/// independently of [`playback_rs`] and the backend can be swapped out
+/// (e.g. muted, or replaced in tests) without touching the rest of the app.
+pub trait AudioBackend {
+    /// Sets the output volume, in the `0.0..=1.0` range.
+    fn set_volume(&mut self, volume: f32);
+    /// Plays `song` immediately, replacing whatever is currently playing.
+    fn play(&mut self, song: &Song);
+}
+
+/// [`AudioBackend`] backed by [`playback_rs::Player`].
+pub struct PlaybackRsBackend {
+    player: Player,
+    volume: f32,
+}
+
+impl PlaybackRsBackend {
+    pub fn new() -> Self {
+        let player = Player::new().expect("couldn't create audio player");
+        Self { player, volume: 1.0 }
+    }
+}
+
+impl Default for PlaybackRsBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioBackend for PlaybackRsBackend {
+    fn set_volume(&mut self, volume: f32) {
+        self.volume = volume.clamp(0.0, 1.0);
+        self.player.set_volume(self.volume);
+    }
+
+    fn play(&mut self, song: &Song) {
+        self.player
+            .play_song_now(song)
+            .unwrap_or_else(|e| warn!("Error playing sound: {e}"));
+    }
+}
+
+/// Builds a short synthesized chime to use as an alert when no sound file
+/// could be loaded, so the app always has a working sound.
+///
+/// `freq_hz`/`duration_secs` fall back to [`DEFAULT_TONE_FREQ_HZ`]/
+/// [`DEFAULT_TONE_DURATION_SECS`] when `None`.
+pub fn fallback_tone(freq_hz: Option<f32>, duration_secs: Option<f32>) -> Option<Song> {
+    let freq_hz = freq_hz.unwrap_or(DEFAULT_TONE_FREQ_HZ);
+    let duration_secs = duration_secs.unwrap_or(DEFAULT_TONE_DURATION_SECS);
+
+    Song::new(generate_tone(freq_hz, duration_secs), TONE_SAMPLE_RATE)
+        .map_err(|e| warn!("Error generating fallback tone: {e}"))
+        .ok()
+}
+
+/// Generates an interleaved stereo PCM buffer for a sine-wave tone, with a
+/// linear fade-in/fade-out envelope to avoid audible clicks at the edges.
+fn generate_tone(freq_hz: f32, duration_secs: f32) -> Vec<f32> {
+    let sample_count = (TONE_SAMPLE_RATE as f32 * duration_secs) as usize;
+    let fade_samples = (TONE_SAMPLE_RATE as f32 * TONE_FADE.as_secs_f32()) as usize;
+
+    let mut samples = Vec::with_capacity(sample_count * 2);
+    for n in 0..sample_count {
+        let envelope = if n < fade_samples {
+            n as f32 / fade_samples as f32
+        } else if n >= sample_count - fade_samples {
+            (sample_count - n) as f32 / fade_samples as f32
+        } else {
+            1.0
+        };
+
+        let t = n as f32 / TONE_SAMPLE_RATE as f32;
+        let sample = TONE_VOLUME * envelope * (2.0 * std::f32::consts::PI * freq_hz * t).sin();
+
+        // duplicate the mono sample across both channels
+        samples.push(sample);
+        samples.push(sample);
+    }
+    samples
+}
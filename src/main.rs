@@ -1,17 +1,22 @@
+mod audio;
+mod timer;
+
+use audio::{AudioBackend, PlaybackRsBackend};
 use directories::ProjectDirs;
+use gtk::glib;
 use gtk::prelude::*;
 use log::*;
-use playback_rs::{Player, Song};
+use notify_rust::Notification;
+use playback_rs::Song;
 use relm4::prelude::*;
+use relm4::WorkerController;
 use relm4_components::open_dialog::*;
 use serde::{Deserialize, Serialize};
 use std::fs::read_to_string;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::sync::{atomic, atomic::AtomicBool, Arc};
 use std::time::Duration;
-
-const SLEEP_STEP: Duration = Duration::from_millis(250);
+use timer::{TimerMsg, TimerOutput, TimerWorker};
 
 const ICON_START: &str = "media-playback-start-symbolic";
 const ICON_PAUSE: &str = "media-playback-pause-symbolic";
@@ -20,7 +25,9 @@ const ICON_RENEW: &str = "media-skip-backward-symbolic";
 const ICON_RESTART: &str = "object-rotate-left-symbolic";
 const ICON_CONFIG: &str = "preferences-system-symbolic"; // maybe applications-system-symbolic
 
-const CONFIG_FILE: &str = "config.bin";
+const CONFIG_FILE: &str = "config.toml";
+/// Name of the pre-TOML config file, kept around only to migrate existing users once.
+const LEGACY_CONFIG_FILE: &str = "config.bin";
 const DEFAULT_SOUND: &str = "default.ogg";
 
 fn main() {
@@ -28,23 +35,51 @@ fn main() {
 
     let dirs = ProjectDirs::from("", "", "pomo-oxide").expect("couldn't get project directories");
     let config_file = dirs.config_dir().join(CONFIG_FILE);
+    let legacy_config_file = dirs.config_dir().join(LEGACY_CONFIG_FILE);
     std::fs::create_dir_all(dirs.config_dir())
         .unwrap_or_else(|e| warn!("Error creating config directory: {e}"));
 
-    let config = {
-        (|| -> Result<Config, Box<dyn std::error::Error>> {
-            let input = read_to_string(&config_file)?;
-            Ok(serde_json::from_str(&input)?)
-        })()
-        .map_err(|e| warn!("Error loading config: {e}"))
-        .unwrap_or_default()
-    };
+    let config = load_config(&config_file, &legacy_config_file);
 
     let model = AppModel::new(config, config_file);
     let app = RelmApp::new("pomo-oxide");
     app.run::<AppModel>(model);
 }
 
+/// Loads the TOML config, migrating it once from the legacy JSON `config.bin` if that's all
+/// that exists yet.
+fn load_config(config_file: &Path, legacy_config_file: &Path) -> Config {
+    if let Ok(input) = read_to_string(config_file) {
+        return toml::from_str(&input)
+            .map_err(|e| warn!("Error loading config: {e}"))
+            .unwrap_or_default();
+    }
+
+    let legacy_config = (|| -> Result<Config, Box<dyn std::error::Error>> {
+        let input = read_to_string(legacy_config_file)?;
+        Ok(serde_json::from_str::<LegacyConfig>(&input)?.into())
+    })()
+    .map_err(|e| warn!("Error migrating legacy config: {e}"))
+    .ok();
+
+    match legacy_config {
+        Some(config) => {
+            info!("Migrating legacy {LEGACY_CONFIG_FILE} to {CONFIG_FILE}");
+            save_config(config_file, &config);
+            config
+        }
+        None => Config::default(),
+    }
+}
+
+fn save_config(config_file: &Path, config: &Config) {
+    (|| -> Result<_, Box<dyn std::error::Error>> {
+        let encoded = toml::to_string_pretty(config)?;
+        Ok(std::fs::write(config_file, encoded)?)
+    })()
+    .unwrap_or_else(|e| warn!("Error saving config: {e}"));
+}
+
 #[relm4::component]
 impl SimpleComponent for AppModel {
     type Input = AppMsg;
@@ -52,30 +87,62 @@ impl SimpleComponent for AppModel {
     type Init = Self;
 
     fn init(
-        model: Self::Init,
+        mut model: Self::Init,
         root: &Self::Root,
         sender: ComponentSender<Self>,
     ) -> ComponentParts<Self> {
-        let song_dialog = OpenDialog::builder()
-            .transient_for_native(root)
-            .launch(OpenDialogSettings::default())
-            .forward(sender.input_sender(), |response| match response {
-                OpenDialogResponse::Accept(path) => {
-                    AppMsg::ChangeConfig(Box::new(move |mut config| {
-                        config.sound_path = path;
-                    }))
-                }
-                OpenDialogResponse::Cancel => AppMsg::Ignore,
+        let song_dialogs: Vec<_> = Transition::ALL
+            .into_iter()
+            .map(|transition| {
+                let dialog = OpenDialog::builder()
+                    .transient_for_native(root)
+                    .launch(OpenDialogSettings::default())
+                    .forward(sender.input_sender(), move |response| match response {
+                        OpenDialogResponse::Accept(path) => {
+                            AppMsg::ChangeConfig(Box::new(move |config| {
+                                transition.set_sound(config, path);
+                            }))
+                        }
+                        OpenDialogResponse::Cancel => AppMsg::Ignore,
+                    });
+                Rc::new(dialog)
+            })
+            .collect();
+
+        let timer_worker = TimerWorker::builder()
+            .detach_worker(())
+            .forward(sender.input_sender(), |output| match output {
+                TimerOutput::Tick(remaining) => AppMsg::Tick(remaining),
+                TimerOutput::Elapsed => AppMsg::Elapsed,
             });
-        let song_dialog = Rc::new(song_dialog);
-        let song_dialog_ref = Rc::clone(&song_dialog);
+        timer_worker.emit(TimerMsg::SetRemaining(model.timer));
+        model.timer_worker = Some(timer_worker);
 
         let widgets = view_output!();
         let popover_ref = widgets.popover.clone();
 
-        widgets.song_btn.connect_clicked(move |_btn| {
+        let popover_ref_clone = popover_ref.clone();
+        let dialog = Rc::clone(&song_dialogs[0]);
+        widgets
+            .pomodoro_to_break_song_btn
+            .connect_clicked(move |_btn| {
+                popover_ref_clone.popdown();
+                dialog.emit(OpenDialogMsg::Open);
+            });
+
+        let popover_ref_clone = popover_ref.clone();
+        let dialog = Rc::clone(&song_dialogs[1]);
+        widgets
+            .break_to_pomodoro_song_btn
+            .connect_clicked(move |_btn| {
+                popover_ref_clone.popdown();
+                dialog.emit(OpenDialogMsg::Open);
+            });
+
+        let dialog = Rc::clone(&song_dialogs[2]);
+        widgets.to_rest_song_btn.connect_clicked(move |_btn| {
             popover_ref.popdown();
-            song_dialog_ref.emit(OpenDialogMsg::Open);
+            dialog.emit(OpenDialogMsg::Open);
         });
 
         widgets.status_bar.push(0, &model.status_bar());
@@ -89,7 +156,7 @@ impl SimpleComponent for AppModel {
     }
 
     additional_fields! {
-        song_dialog: Rc<Controller<OpenDialogInner<SingleSelection>>>,
+        song_dialogs: Vec<Rc<Controller<OpenDialogInner<SingleSelection>>>>,
     }
 
     view! {
@@ -174,16 +241,94 @@ impl SimpleComponent for AppModel {
                                 } @toggle_handler_3,
                             },
 
+                            gtk::Scale {
+                                set_tooltip_text: Some("Volume"),
+                                set_range: (0.0, 1.0),
+                                set_digits: 2,
+                                #[watch]
+                                #[block_signal(toggle_handler_volume)]
+                                set_value: model.config.volume as f64,
+                                set_increments: (0.05, 0.1),
+
+                                connect_value_changed[sender] => move |scale| {
+                                    let value = scale.value() as f32;
+                                    sender.input(AppMsg::ChangeConfig(Box::new(move |config|
+                                        config.volume = value
+                                    )))
+                                } @toggle_handler_volume,
+                            },
+
                             gtk::Box {
                                 set_orientation: gtk::Orientation::Horizontal,
                                 set_spacing: 10,
 
-                                #[name = "song_btn"]
-                                gtk::Button::with_label("Change Sound") {
+                                gtk::Label {
+                                    set_label: "Notifications",
+                                },
+
+                                gtk::Switch {
+                                    set_tooltip_text: Some("Show a desktop notification when a phase ends"),
                                     #[watch]
-                                    set_tooltip_text: Some(&format!("Current file: {:?}",
-                                        model.config.sound_path.file_name().unwrap_or_default()
-                                    )),
+                                    #[block_signal(toggle_handler_4)]
+                                    set_active: model.config.notifications_enabled,
+
+                                    connect_state_set[sender] => move |_switch, state| {
+                                        sender.input(AppMsg::ChangeConfig(Box::new(move |config|
+                                            config.notifications_enabled = state
+                                        )));
+                                        glib::Propagation::Proceed
+                                    } @toggle_handler_4,
+                                },
+
+                                gtk::DropDown::from_strings(&["Low", "Normal", "Critical"]) {
+                                    set_tooltip_text: Some("Notification urgency"),
+                                    #[watch]
+                                    #[block_signal(toggle_handler_5)]
+                                    set_selected: model.config.notification_urgency as u32,
+
+                                    connect_selected_notify[sender] => move |dropdown| {
+                                        let urgency = match dropdown.selected() {
+                                            0 => Urgency::Low,
+                                            2 => Urgency::Critical,
+                                            _ => Urgency::Normal,
+                                        };
+                                        sender.input(AppMsg::ChangeConfig(Box::new(move |config|
+                                            config.notification_urgency = urgency
+                                        )));
+                                    } @toggle_handler_5,
+                                },
+                            },
+
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Horizontal,
+                                set_spacing: 10,
+
+                                #[name = "pomodoro_to_break_song_btn"]
+                                gtk::Button::with_label("Pomodoro → Break Sound") {
+                                    #[watch]
+                                    set_tooltip_text: Some(&sound_tooltip(&model.config.pomodoro_to_break_sound)),
+                                },
+                            },
+
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Horizontal,
+                                set_spacing: 10,
+
+                                #[name = "break_to_pomodoro_song_btn"]
+                                gtk::Button::with_label("Break/Rest → Pomodoro Sound") {
+                                    #[watch]
+                                    set_tooltip_text: Some(&sound_tooltip(&model.config.break_to_pomodoro_sound)),
+                                },
+                            },
+
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Horizontal,
+                                set_spacing: 10,
+
+                                #[name = "to_rest_song_btn"]
+                                gtk::Button::with_label("→ Rest Sound") {
+                                    #[watch]
+                                    set_tooltip_text: Some(&sound_tooltip(&model.config.to_rest_sound)),
                                 },
 
                                 gtk::Button {
@@ -263,56 +408,49 @@ impl SimpleComponent for AppModel {
         }
     }
 
-    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
-        self.clear_step_permission();
-
+    fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>) {
         match message {
             AppMsg::Ignore => return,
-            AppMsg::Step => self.try_next_state(),
+            AppMsg::Tick(remaining) => self.timer = remaining,
+            AppMsg::Elapsed => self.next_state(),
             AppMsg::Toggle(toggle) => self.toggle(toggle),
             AppMsg::Skip => self.next_state(),
             AppMsg::Renew => self.restart_state(),
             AppMsg::Restart => self.restart(),
             AppMsg::ChangeConfig(config_changer) => self.change_config(config_changer),
         }
-
-        if self.running {
-            let duration = SLEEP_STEP.min(self.timer);
-            self.timer -= duration;
-
-            self.clear_step_permission();
-            let perm = Arc::new(AtomicBool::new(true));
-            self.step_permission = Some(Arc::clone(&perm));
-
-            std::thread::spawn(move || {
-                std::thread::sleep(duration);
-                // if we still have permission, send step
-                if perm.load(atomic::Ordering::SeqCst) {
-                    sender.input(AppMsg::Step);
-                }
-            });
-        }
     }
 }
 
 struct AppModel {
     running: bool,
     timer: Duration,
-    player: Player,
-    song: Option<Song>,
+    audio: Box<dyn AudioBackend>,
+    pomodoro_to_break_song: Option<Song>,
+    break_to_pomodoro_song: Option<Song>,
+    to_rest_song: Option<Song>,
     state: State,
     rest_counter: u8,
     pomodoro_count: usize,
     config: Config,
     config_file: PathBuf,
-    /// Stores if a running thread has permission to send AppMsg::Step
-    step_permission: Option<Arc<AtomicBool>>,
+    /// Background worker that owns the countdown loop. `None` until [`init`](
+    /// SimpleComponent::init) wires it up, since it needs the component's `ComponentSender`.
+    timer_worker: Option<WorkerController<TimerWorker>>,
 }
 
 impl AppModel {
     fn new(config: Config, config_file: PathBuf) -> Self {
-        let player = Player::new().expect("couldn't create audio player");
-        let song = try_song(&config.sound_path);
+        let mut audio: Box<dyn AudioBackend> = Box::new(PlaybackRsBackend::new());
+        audio.set_volume(config.volume);
+        let [pomodoro_to_break_song, break_to_pomodoro_song, to_rest_song] =
+            Transition::ALL.map(|transition| {
+                load_song(
+                    transition.sound(&config),
+                    config.tone_freq_hz,
+                    config.tone_duration_secs,
+                )
+            });
         let state = State::default();
         let timer = state.duration(&config);
         Self {
@@ -323,14 +461,30 @@ impl AppModel {
             pomodoro_count: 0,
             config,
             config_file,
-            step_permission: None,
-            player,
-            song,
+            timer_worker: None,
+            audio,
+            pomodoro_to_break_song,
+            break_to_pomodoro_song,
+            to_rest_song,
         }
     }
+
+    /// Pushes the model's current running state and remaining time to the timer worker.
+    fn sync_timer_worker(&self) {
+        let Some(worker) = self.timer_worker.as_ref() else {
+            return;
+        };
+
+        worker.emit(TimerMsg::SetRemaining(self.timer));
+        worker.emit(if self.running {
+            TimerMsg::Start
+        } else {
+            TimerMsg::Pause
+        });
+    }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 enum State {
     #[default]
     Pomodoro,
@@ -367,6 +521,54 @@ impl State {
     }
 }
 
+/// One of the three user-configurable sound transitions, naming both the [`Config`] sound-path
+/// field and the [`AppModel`] pre-loaded [`Song`] it goes with.
+#[derive(Debug, Clone, Copy)]
+enum Transition {
+    PomodoroToBreak,
+    BreakToPomodoro,
+    ToRest,
+}
+
+impl Transition {
+    const ALL: [Self; 3] = [Self::PomodoroToBreak, Self::BreakToPomodoro, Self::ToRest];
+
+    /// Which transition plays when the phase changes from `from` to `to`, if any.
+    fn from_states(from: State, to: State) -> Option<Self> {
+        match (from, to) {
+            (State::Pomodoro, State::Break) => Some(Self::PomodoroToBreak),
+            (State::Pomodoro, State::Rest) => Some(Self::ToRest),
+            (State::Break | State::Rest, State::Pomodoro) => Some(Self::BreakToPomodoro),
+            _ => None,
+        }
+    }
+
+    fn sound(self, config: &Config) -> &Option<PathBuf> {
+        match self {
+            Self::PomodoroToBreak => &config.pomodoro_to_break_sound,
+            Self::BreakToPomodoro => &config.break_to_pomodoro_sound,
+            Self::ToRest => &config.to_rest_sound,
+        }
+    }
+
+    fn set_sound(self, config: &mut Config, path: PathBuf) {
+        let field = match self {
+            Self::PomodoroToBreak => &mut config.pomodoro_to_break_sound,
+            Self::BreakToPomodoro => &mut config.break_to_pomodoro_sound,
+            Self::ToRest => &mut config.to_rest_sound,
+        };
+        *field = Some(path);
+    }
+
+    fn song(self, model: &AppModel) -> &Option<Song> {
+        match self {
+            Self::PomodoroToBreak => &model.pomodoro_to_break_song,
+            Self::BreakToPomodoro => &model.break_to_pomodoro_song,
+            Self::ToRest => &model.to_rest_song,
+        }
+    }
+}
+
 impl AppModel {
     fn state_duration(&self) -> Duration {
         self.state.duration(&self.config)
@@ -385,21 +587,11 @@ impl AppModel {
 
     fn toggle(&mut self, running: Option<bool>) {
         self.running = running.unwrap_or(!self.running);
-    }
-
-    fn clear_step_permission(&mut self) {
-        if let Some(perm) = self.step_permission.take() {
-            perm.store(false, atomic::Ordering::SeqCst);
-        }
-    }
-
-    fn try_next_state(&mut self) {
-        if self.timer.is_zero() {
-            self.next_state()
-        }
+        self.sync_timer_worker();
     }
 
     fn next_state(&mut self) {
+        let from = self.state;
         self.state = match self.state {
             State::Pomodoro => {
                 // avoid counting "skips" as complete pomodoros
@@ -420,46 +612,94 @@ impl AppModel {
             }
         };
         if self.timer.is_zero() {
-            if let Some(song) = self.song.as_ref() {
-                self.player.play_song_now(song).unwrap();
+            if let Some(song) = self.transition_song(from, self.state) {
+                self.audio.play(song);
             }
+            self.notify_transition(from, self.state);
         }
         self.restart_state()
     }
 
+    /// Picks the [`Song`] to play for the transition from `from` to `to`.
+    fn transition_song(&self, from: State, to: State) -> Option<&Song> {
+        Transition::from_states(from, to)?.song(self).as_ref()
+    }
+
+    /// Fires a desktop notification announcing that `from` just finished and `to` is starting.
+    fn notify_transition(&self, from: State, to: State) {
+        if !self.config.notifications_enabled {
+            return;
+        }
+
+        let summary = format!("{} complete", from);
+        let body = format!(
+            "Time for a {} ({})",
+            to,
+            min_format(&to.duration(&self.config))
+        );
+
+        Notification::new()
+            .summary(&summary)
+            .body(&body)
+            .urgency(self.config.notification_urgency.to_notify())
+            .show()
+            .map(|_| ())
+            .unwrap_or_else(|e| warn!("Error showing notification: {e}"));
+    }
+
     fn restart_state(&mut self) {
         self.timer = self.state_duration();
         info!("Starting {:?} - {}", &self.state, min_format(&self.timer));
+        self.sync_timer_worker();
     }
 
     fn restart(&mut self) {
         self.state = State::Pomodoro;
         self.rest_counter = 0;
+        if let Some(worker) = self.timer_worker.as_ref() {
+            worker.emit(TimerMsg::Reset);
+        }
         self.restart_state();
     }
 
     fn change_config(&mut self, config_changer: Box<dyn FnOnce(&mut Config) + Send>) {
-        let previous_sound_path = self.config.sound_path.clone();
+        let previous_sounds: Vec<Option<PathBuf>> = Transition::ALL
+            .iter()
+            .map(|&transition| transition.sound(&self.config).clone())
+            .collect();
 
         config_changer(&mut self.config);
 
-        if previous_sound_path != self.config.sound_path {
-            match try_song(&self.config.sound_path) {
-                Some(new_song) => self.song = Some(new_song),
-                None => self.config.sound_path = previous_sound_path,
+        self.audio.set_volume(self.config.volume);
+
+        let tone_freq_hz = self.config.tone_freq_hz;
+        let tone_duration_secs = self.config.tone_duration_secs;
+
+        for (transition, previous) in Transition::ALL.into_iter().zip(previous_sounds) {
+            if *transition.sound(&self.config) == previous {
+                continue;
+            }
+
+            let (path, song) = match transition {
+                Transition::PomodoroToBreak => (
+                    &mut self.config.pomodoro_to_break_sound,
+                    &mut self.pomodoro_to_break_song,
+                ),
+                Transition::BreakToPomodoro => (
+                    &mut self.config.break_to_pomodoro_sound,
+                    &mut self.break_to_pomodoro_song,
+                ),
+                Transition::ToRest => (&mut self.config.to_rest_sound, &mut self.to_rest_song),
             };
 
-            if let Some(song) = self.song.as_ref() {
-                self.player.play_song_now(song).unwrap();
+            reload_transition_sound(path, song, previous, tone_freq_hz, tone_duration_secs);
+            if let Some(song) = song.as_ref() {
+                self.audio.play(song);
             }
         }
 
         info!("Saving config");
-        (|| -> Result<_, Box<dyn std::error::Error>> {
-            let encoded = serde_json::to_vec_pretty(&self.config)?;
-            Ok(std::fs::write(&self.config_file, encoded)?)
-        })()
-        .unwrap_or_else(|e| warn!("Error saving config: {e}"));
+        save_config(&self.config_file, &self.config);
 
         if self.config.rest_count <= self.rest_counter {
             self.restart();
@@ -471,7 +711,8 @@ impl AppModel {
 
 enum AppMsg {
     Ignore,
-    Step,
+    Tick(Duration),
+    Elapsed,
     Toggle(Option<bool>),
     Skip,
     Renew,
@@ -483,7 +724,8 @@ impl std::fmt::Debug for AppMsg {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Ignore => write!(f, "Ignore"),
-            Self::Step => write!(f, "Step"),
+            Self::Tick(arg0) => f.debug_tuple("Tick").field(arg0).finish(),
+            Self::Elapsed => write!(f, "Elapsed"),
             Self::Toggle(arg0) => f.debug_tuple("Toggle").field(arg0).finish(),
             Self::Skip => write!(f, "Skip"),
             Self::Renew => write!(f, "Renew"),
@@ -496,15 +738,39 @@ impl std::fmt::Debug for AppMsg {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 struct Config {
     /// Time for each pomodoro.
+    #[serde(with = "duration_secs")]
     pomodoro_time: Duration,
     /// Time for each break.
+    #[serde(with = "duration_secs")]
     break_time: Duration,
     /// Time for each rest.
+    #[serde(with = "duration_secs")]
     rest_time: Duration,
     /// How many pomodoros until the break will be a rest.
     rest_count: u8,
-    /// Sound file path
-    sound_path: PathBuf,
+    /// Sound played when a Pomodoro ends and a Break starts. `None` plays nothing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pomodoro_to_break_sound: Option<PathBuf>,
+    /// Sound played when a Break or Rest ends and a new Pomodoro starts. `None` plays nothing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    break_to_pomodoro_sound: Option<PathBuf>,
+    /// Sound played when a Pomodoro ends and a Rest starts instead of a Break. `None` plays nothing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to_rest_sound: Option<PathBuf>,
+    /// Playback volume for the transition sounds, in the `0.0..=1.0` range.
+    volume: f32,
+    /// Whether a desktop notification is shown when a phase ends.
+    notifications_enabled: bool,
+    /// Urgency hint used for phase-end notifications.
+    notification_urgency: Urgency,
+    /// Frequency, in Hz, of the synthesized fallback chime played when no sound file
+    /// could be loaded. `None` uses [`audio::DEFAULT_TONE_FREQ_HZ`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tone_freq_hz: Option<f32>,
+    /// Duration, in seconds, of the synthesized fallback chime. `None` uses
+    /// [`audio::DEFAULT_TONE_DURATION_SECS`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tone_duration_secs: Option<f32>,
 }
 
 impl Default for Config {
@@ -514,7 +780,74 @@ impl Default for Config {
             break_time: Duration::from_secs(60 * 5),
             rest_time: Duration::from_secs(60 * 20),
             rest_count: 4,
-            sound_path: DEFAULT_SOUND.into(),
+            pomodoro_to_break_sound: Some(DEFAULT_SOUND.into()),
+            break_to_pomodoro_sound: Some(DEFAULT_SOUND.into()),
+            to_rest_sound: Some(DEFAULT_SOUND.into()),
+            volume: 1.0,
+            notifications_enabled: true,
+            notification_urgency: Urgency::default(),
+            tone_freq_hz: None,
+            tone_duration_secs: None,
+        }
+    }
+}
+
+/// Mirrors the on-disk shape of the pre-TOML `config.bin`, so migrating it doesn't have to
+/// fight [`Config`]'s current field set and `#[serde(with = "duration_secs")]` encoding.
+#[derive(Debug, Deserialize)]
+struct LegacyConfig {
+    pomodoro_time: Duration,
+    break_time: Duration,
+    rest_time: Duration,
+    rest_count: u8,
+    sound_path: PathBuf,
+}
+
+impl From<LegacyConfig> for Config {
+    fn from(legacy: LegacyConfig) -> Self {
+        Self {
+            pomodoro_time: legacy.pomodoro_time,
+            break_time: legacy.break_time,
+            rest_time: legacy.rest_time,
+            rest_count: legacy.rest_count,
+            pomodoro_to_break_sound: Some(legacy.sound_path.clone()),
+            break_to_pomodoro_sound: Some(legacy.sound_path.clone()),
+            to_rest_sound: Some(legacy.sound_path),
+            ..Config::default()
+        }
+    }
+}
+
+/// (De)serializes a [`Duration`] as a plain integer number of seconds, so it reads as a normal
+/// number in the TOML config file instead of a nested table.
+mod duration_secs {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        duration.as_secs().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        Ok(Duration::from_secs(u64::deserialize(deserializer)?))
+    }
+}
+
+/// Urgency hint for desktop notifications, mirroring [`notify_rust::Urgency`].
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize)]
+enum Urgency {
+    Low,
+    #[default]
+    Normal,
+    Critical,
+}
+
+impl Urgency {
+    fn to_notify(self) -> notify_rust::Urgency {
+        match self {
+            Urgency::Low => notify_rust::Urgency::Low,
+            Urgency::Normal => notify_rust::Urgency::Normal,
+            Urgency::Critical => notify_rust::Urgency::Critical,
         }
     }
 }
@@ -530,8 +863,42 @@ fn min_as_markup(s: String) -> String {
     format!("<span font=\"Sans Bold 64\">{}</span>", s)
 }
 
-fn try_song(path: &PathBuf) -> Option<Song> {
+fn sound_tooltip(path: &Option<PathBuf>) -> String {
+    match path {
+        Some(path) => format!("Current file: {:?}", path.file_name().unwrap_or_default()),
+        None => "No sound".to_string(),
+    }
+}
+
+fn try_song(path: &PathBuf, tone_freq_hz: Option<f32>, tone_duration_secs: Option<f32>) -> Option<Song> {
     Song::from_file(path)
         .map_err(|e| warn!("Failed to open audio file: {e}"))
         .ok()
+        .or_else(|| audio::fallback_tone(tone_freq_hz, tone_duration_secs))
+}
+
+/// Loads the [`Song`] for an optional transition sound path. `None` means no sound plays.
+fn load_song(
+    path: &Option<PathBuf>,
+    tone_freq_hz: Option<f32>,
+    tone_duration_secs: Option<f32>,
+) -> Option<Song> {
+    path.as_ref()
+        .and_then(|path| try_song(path, tone_freq_hz, tone_duration_secs))
+}
+
+/// Reloads `song` from `path`, falling back to `previous` if the new path fails to produce any
+/// sound at all (the synthesized fallback tone means this should essentially never happen).
+fn reload_transition_sound(
+    path: &mut Option<PathBuf>,
+    song: &mut Option<Song>,
+    previous: Option<PathBuf>,
+    tone_freq_hz: Option<f32>,
+    tone_duration_secs: Option<f32>,
+) {
+    match load_song(path, tone_freq_hz, tone_duration_secs) {
+        Some(new_song) => *song = Some(new_song),
+        None if path.is_some() => *path = previous,
+        None => *song = None,
+    }
 }
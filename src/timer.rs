@@ -0,0 +1,82 @@
+use relm4::{ComponentSender, Worker};
+use std::time::Duration;
+
+/// How often the background countdown thread wakes up to check for elapsed time.
+const SLEEP_STEP: Duration = Duration::from_millis(250);
+
+/// Commands accepted by [`TimerWorker`].
+#[derive(Debug)]
+pub enum TimerMsg {
+    /// Resume counting down.
+    Start,
+    /// Stop counting down, keeping the current remaining time.
+    Pause,
+    /// Overwrite the remaining time, e.g. when skipping or restarting a phase.
+    SetRemaining(Duration),
+    /// Stop counting down and clear the remaining time.
+    Reset,
+    Tick,
+}
+
+/// Outputs emitted by [`TimerWorker`] back to its owning component.
+#[derive(Debug)]
+pub enum TimerOutput {
+    /// The remaining time changed; carries the new value for display.
+    Tick(Duration),
+    /// The remaining time reached zero.
+    Elapsed,
+}
+
+/// Background worker that owns the countdown loop and talks to [`AppModel`](crate::AppModel)
+/// purely through [`TimerMsg`]/[`TimerOutput`], instead of a thread respawned on every step
+/// and an `AtomicBool` shared across it.
+pub struct TimerWorker {
+    running: bool,
+    remaining: Duration,
+}
+
+impl Worker for TimerWorker {
+    type Init = ();
+    type Input = TimerMsg;
+    type Output = TimerOutput;
+
+    fn init(_init: Self::Init, sender: ComponentSender<Self>) -> Self {
+        std::thread::spawn(move || loop {
+            std::thread::sleep(SLEEP_STEP);
+            sender.input(TimerMsg::Tick);
+        });
+
+        Self {
+            running: false,
+            remaining: Duration::ZERO,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            TimerMsg::Start => self.running = true,
+            TimerMsg::Pause => self.running = false,
+            TimerMsg::SetRemaining(remaining) => self.remaining = remaining,
+            TimerMsg::Reset => {
+                self.running = false;
+                self.remaining = Duration::ZERO;
+            }
+            TimerMsg::Tick => {
+                if !self.running {
+                    return;
+                }
+
+                self.remaining -= SLEEP_STEP.min(self.remaining);
+                sender.output(TimerOutput::Tick(self.remaining)).ok();
+
+                if self.remaining.is_zero() {
+                    // Stop ticking until the component resyncs us via `SetRemaining`/`Start`,
+                    // otherwise we'd keep emitting `Elapsed` every `SLEEP_STEP` for as long as
+                    // it takes the component to react (it may run a blocking notification first).
+                    self.running = false;
+                    sender.output(TimerOutput::Elapsed).ok();
+                }
+            }
+        }
+    }
+}